@@ -1,3 +1,6 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use std::{mem, thread};
 
 use crossbeam_channel::{unbounded, Receiver, Sender};
@@ -6,11 +9,143 @@ use peek_poke::{
     Poke,
 };
 
+/// Length of a single chunk frame, excluding its own 2-byte header.
+type ChunkLength = u16;
+
+/// A display list larger than this is split across multiple frames.
+const MAX_CHUNK_LENGTH: ChunkLength = 0x4000;
+
+/// Set on a frame's length header when more frames follow for the same display list.
+const CHUNK_HAS_CONTINUATION: ChunkLength = 0x8000;
+
+/// Scheduling priority for a display list's chunks, lowest value first.
+type RequestPriority = u8;
+
+/// Foreground/visible content; drained before `PRIO_NORMAL` and `PRIO_BACKGROUND`.
+const PRIO_HIGH: RequestPriority = 0x20;
+/// Priority of a `DisplayList` none of whose items was pushed with an explicit priority.
+const PRIO_NORMAL: RequestPriority = 0x40;
+/// Off-screen or prefetched content; only drained once nothing higher is queued.
+const PRIO_BACKGROUND: RequestPriority = 0x80;
+
+/// High watermark: once the backend's unprocessed backlog exceeds this many
+/// bytes it asks producers to pause.
+const MAX_BUFFER_SIZE: usize = 32 * 1024;
+
+/// Low watermark: the backend resumes producers once the backlog drains back
+/// below this.
+const LOW_WATERMARK: usize = MAX_BUFFER_SIZE / 2;
+
+/// Flow-control signal sent from `Backend` back to producers over a
+/// dedicated channel, generalizing the one-shot `result_sender` ack into
+/// continuous credit-based backpressure.
+#[derive(Clone, Copy)]
+enum PayloadStatus {
+    /// The backlog has drained; it's safe to resume sending.
+    Read,
+    /// The backlog is over `MAX_BUFFER_SIZE`; stop sending until `Read`.
+    Pause,
+    /// The backend is gone; further sends would never be read.
+    Dropped,
+}
+
+/// Wraps a raw `Sender<Message>` with the producer side of the flow-control
+/// handshake: `send` blocks while the backend has signalled `Pause`.
+struct FlowControlledSender {
+    sender: Sender<Message>,
+    status_receiver: Receiver<PayloadStatus>,
+    paused: bool,
+}
+
+impl FlowControlledSender {
+    fn new(sender: Sender<Message>, status_receiver: Receiver<PayloadStatus>) -> Self {
+        FlowControlledSender {
+            sender,
+            status_receiver,
+            paused: false,
+        }
+    }
+
+    fn send(&mut self, message: Message) {
+        loop {
+            while let Ok(status) = self.status_receiver.try_recv() {
+                self.apply(status);
+            }
+            if !self.paused {
+                break;
+            }
+            match self.status_receiver.recv() {
+                Ok(status) => self.apply(status),
+                Err(_) => break,
+            }
+        }
+        self.sender.send(message).expect("Could not send message");
+    }
+
+    fn apply(&mut self, status: PayloadStatus) {
+        match status {
+            PayloadStatus::Pause => self.paused = true,
+            PayloadStatus::Read | PayloadStatus::Dropped => self.paused = false,
+        }
+    }
+}
+
 enum Message {
-    SetDisplayList(DisplayList),
+    /// A single framed slice of a `DisplayList`'s payload, tagged with the
+    /// list's priority and the producer-assigned `seq` of the list it
+    /// belongs to: a 2-byte big-endian header (length, with
+    /// `CHUNK_HAS_CONTINUATION` set while more frames follow) followed by up
+    /// to `MAX_CHUNK_LENGTH` payload bytes.
+    DisplayListChunk {
+        priority: RequestPriority,
+        seq: u64,
+        frame: Vec<u8>,
+    },
     Close,
 }
 
+impl Message {
+    /// `Close` is always treated as high priority so a pending shutdown isn't
+    /// starved behind a backlog of queued display lists.
+    fn priority(&self) -> RequestPriority {
+        match self {
+            Message::DisplayListChunk { priority, .. } => *priority,
+            Message::Close => PRIO_HIGH,
+        }
+    }
+}
+
+/// Per-priority FIFO queue feeding `Backend::run`. `pop` always drains the
+/// lowest-numbered (highest-priority) non-empty queue first, so foreground
+/// display lists can preempt background ones queued earlier.
+struct SendQueue {
+    queues: BTreeMap<RequestPriority, VecDeque<Message>>,
+}
+
+impl SendQueue {
+    fn new() -> Self {
+        SendQueue {
+            queues: BTreeMap::new(),
+        }
+    }
+
+    fn push(&mut self, message: Message) {
+        self.queues
+            .entry(message.priority())
+            .or_default()
+            .push_back(message);
+    }
+
+    fn pop(&mut self) -> Option<Message> {
+        let mut entry = self.queues.first_entry()?;
+        let message = entry.get_mut().pop_front();
+        if entry.get().is_empty() {
+            entry.remove();
+        }
+        message
+    }
+}
+
 #[derive(PeekPoke, Default, Debug)]
 struct RectItem {
     min: (f32, f32),
@@ -27,27 +162,36 @@ enum DisplayListItem {
 
 struct DisplayList {
     payload: Vec<u8>,
+    priority: Option<RequestPriority>,
 }
 
 impl DisplayList {
     fn new() -> Self {
         DisplayList {
             payload: Vec::new(),
+            priority: None,
         }
     }
 
-    fn push_item(&mut self, item: &DisplayListItem) {
+    fn push_item(&mut self, item: &DisplayListItem, priority: Option<RequestPriority>) {
+        // Smaller `RequestPriority` values are more urgent, so a list carries the
+        // highest priority of any item pushed into it rather than whichever was
+        // pushed last: a foreground rect must still preempt a background one even
+        // if it was pushed first.
+        if let Some(priority) = priority {
+            self.priority = Some(self.priority.map_or(priority, |p| p.min(priority)));
+        }
         poke_into_vec(item, &mut self.payload);
         println!("Set DisplayItem in main thread: {:?}", item);
     }
 
-    fn push_list<I>(&mut self, list: I)
+    fn push_list<I>(&mut self, list: I, priority: Option<RequestPriority>)
     where
         I: IntoIterator,
         I::IntoIter: ExactSizeIterator,
         I::Item: Poke,
     {
-        self.push_item(&DisplayListItem::List);
+        self.push_item(&DisplayListItem::List, priority);
         self.push_iter(list);
     }
 
@@ -82,11 +226,210 @@ impl DisplayList {
         ensure_red_zone::<DisplayListItem>(&mut self.payload);
     }
 
-    fn iter(&self) -> DisplayListIter {
-        DisplayListIter::new(&self.payload)
+    /// Slice `self.payload` into fixed-size frames and send them one at a time,
+    /// so the backend can start `process`-ing before this list has finished
+    /// building. Frames are capped at `MAX_CHUNK_LENGTH` just like any other;
+    /// the red zone is never split mid-item because the reader doesn't start
+    /// peeking items until the continuation bit clears and the whole payload
+    /// has been reassembled.
+    ///
+    /// `seq` identifies this list among the lists sent by all producers, so
+    /// the backend can reorder lists that arrive out of order when multiple
+    /// producer threads share one channel. `sender` blocks while the backend
+    /// has signalled `PayloadStatus::Pause`.
+    fn send_chunked(&self, seq: u64, sender: &mut FlowControlledSender) {
+        let mut offset = 0;
+        while offset < self.payload.len() {
+            let remaining = self.payload.len() - offset;
+            let take = remaining.min(MAX_CHUNK_LENGTH as usize);
+            let end = offset + take;
+            let has_continuation = end < self.payload.len();
+
+            let mut header = take as ChunkLength;
+            if has_continuation {
+                header |= CHUNK_HAS_CONTINUATION;
+            }
+
+            let mut frame = Vec::with_capacity(mem::size_of::<ChunkLength>() + take);
+            frame.extend_from_slice(&header.to_be_bytes());
+            frame.extend_from_slice(&self.payload[offset..end]);
+
+            sender.send(Message::DisplayListChunk {
+                priority: self.priority.unwrap_or(PRIO_NORMAL),
+                seq,
+                frame,
+            });
+
+            offset = end;
+        }
     }
 }
 
+/// Distributes a display list's top-level items across a fixed pool of
+/// `DisplayList` partitions, one per backend worker. Each partition keeps its
+/// own payload, so it frames and reassembles independently of the others;
+/// a `push_list` group is always kept on a single partition rather than
+/// being split across workers.
+struct PartitionedDisplayList {
+    partitions: Vec<DisplayList>,
+    next_partition: usize,
+}
+
+impl PartitionedDisplayList {
+    /// `seed` offsets the round-robin starting point, so that successive
+    /// `PartitionedDisplayList`s (e.g. one per `seq`) don't all start
+    /// handing their first round-robined item to the same partition.
+    fn new(partition_count: usize, seed: usize) -> Self {
+        assert!(partition_count > 0, "partition_count must be at least 1");
+        PartitionedDisplayList {
+            partitions: (0..partition_count).map(|_| DisplayList::new()).collect(),
+            next_partition: seed % partition_count,
+        }
+    }
+
+    /// `RectItem`s are bucketed by a spatial key so nearby rects tend to land
+    /// on the same worker; everything else round-robins.
+    fn push_item(&mut self, item: &DisplayListItem, priority: Option<RequestPriority>) {
+        let index = match item {
+            DisplayListItem::Rect(rect) => self.spatial_bucket(rect),
+            _ => self.next_round_robin(),
+        };
+        self.partitions[index].push_item(item, priority);
+    }
+
+    fn push_list<I>(&mut self, list: I, priority: Option<RequestPriority>)
+    where
+        I: IntoIterator,
+        I::IntoIter: ExactSizeIterator,
+        I::Item: Poke,
+    {
+        let index = self.next_round_robin();
+        self.partitions[index].push_list(list, priority);
+    }
+
+    fn end(&mut self) {
+        for partition in &mut self.partitions {
+            partition.end();
+        }
+    }
+
+    /// Sends each partition's payload as its own independently framed chunk
+    /// stream, one partition to each entry in `senders`.
+    ///
+    /// Every partition must send at least one frame for every `seq`: each
+    /// worker's `ReorderBuffer` advances `next_expected` one `seq` at a time,
+    /// so a partition that silently skipped a `seq` would stall that
+    /// worker's reorder buffer forever. `end()` unconditionally red-zones
+    /// every partition, which is what guarantees a non-empty payload here; a
+    /// future partitioner that can leave a partition with zero items must
+    /// still call `send_chunked` for it rather than omitting the send.
+    fn send_chunked(&self, seq: u64, senders: &mut [FlowControlledSender]) {
+        debug_assert_eq!(senders.len(), self.partitions.len());
+        for (partition, sender) in self.partitions.iter().zip(senders) {
+            debug_assert!(
+                !partition.payload.is_empty(),
+                "every partition must send at least one frame per seq, or its \
+                 worker's ReorderBuffer will stall waiting for this seq"
+            );
+            partition.send_chunked(seq, sender);
+        }
+    }
+
+    fn next_round_robin(&mut self) -> usize {
+        let index = self.next_partition;
+        self.next_partition = (self.next_partition + 1) % self.partitions.len();
+        index
+    }
+
+    fn spatial_bucket(&mut self, rect: &RectItem) -> usize {
+        const BUCKET_WIDTH: f32 = 200.0;
+        let bucket = (rect.min.0 / BUCKET_WIDTH).max(0.0) as usize;
+        bucket % self.partitions.len()
+    }
+}
+
+/// Reassembles the interleaved chunk streams of however many display lists
+/// are currently in flight (one per `seq`), since chunks from different
+/// producers can arrive interleaved on the shared channel.
+struct ChunkReassembler {
+    partial: HashMap<u64, Vec<u8>>,
+}
+
+impl ChunkReassembler {
+    fn new() -> Self {
+        ChunkReassembler {
+            partial: HashMap::new(),
+        }
+    }
+
+    /// Feed one frame in for the given `seq`. Returns that list's completed
+    /// payload once the frame whose continuation bit is clear arrives.
+    fn push_frame(&mut self, seq: u64, frame: &[u8]) -> Option<Vec<u8>> {
+        let header = ChunkLength::from_be_bytes([frame[0], frame[1]]);
+        let has_continuation = header & CHUNK_HAS_CONTINUATION != 0;
+        let len = (header & !CHUNK_HAS_CONTINUATION) as usize;
+
+        let buffer = self.partial.entry(seq).or_default();
+        buffer.extend_from_slice(&frame[2..2 + len]);
+
+        if has_continuation {
+            None
+        } else {
+            self.partial.remove(&seq)
+        }
+    }
+}
+
+/// Buffers completed display list payloads keyed by `seq` until their
+/// predecessors have been handed off, so the backend processes lists in a
+/// deterministic order regardless of which producer thread finished first.
+///
+/// Assumes every `seq` in `0..next_expected`'s continuation is eventually
+/// sent to this worker, i.e. no partition ever decides to send nothing for a
+/// `seq` it was handed (see `PartitionedDisplayList::send_chunked`) — a gap
+/// here stalls `pop_ready` on that `seq` forever.
+struct ReorderBuffer {
+    pending: BTreeMap<u64, Vec<u8>>,
+    next_expected: u64,
+}
+
+impl ReorderBuffer {
+    fn new() -> Self {
+        ReorderBuffer {
+            pending: BTreeMap::new(),
+            next_expected: 0,
+        }
+    }
+
+    fn insert(&mut self, seq: u64, payload: Vec<u8>) {
+        self.pending.insert(seq, payload);
+    }
+
+    /// Returns the next in-order payload if it has already arrived, stopping
+    /// at the first gap.
+    fn pop_ready(&mut self) -> Option<Vec<u8>> {
+        let payload = self.pending.remove(&self.next_expected)?;
+        self.next_expected += 1;
+        Some(payload)
+    }
+}
+
+/// A `List` whose declared byte-size doesn't fit within its parent's
+/// remaining data.
+#[derive(Debug)]
+struct TruncatedListError;
+
+impl std::fmt::Display for TruncatedListError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "a nested list's declared byte-size exceeds its parent's remaining data"
+        )
+    }
+}
+
+impl std::error::Error for TruncatedListError {}
+
 struct DisplayListIter<'a> {
     data: &'a [u8],
 }
@@ -96,12 +439,22 @@ impl<'a> DisplayListIter<'a> {
         DisplayListIter { data }
     }
 
+    /// Strips the `usize` item-count header a nested list's region starts
+    /// with, leaving just its items followed by its red zone.
+    fn skip_len_header(mut self) -> Self {
+        if self.data.len() >= mem::size_of::<usize>() {
+            let mut len = 0usize;
+            self.data = peek_from_slice(self.data, &mut len);
+        }
+        self
+    }
+
     fn next_payload_as_item<'b>(
         &'b mut self,
         item: DisplayListItem,
-    ) -> (Option<DisplayListItem>, Option<&'a [u8]>) {
+    ) -> Result<(Option<DisplayListItem>, Option<DisplayListIter<'a>>), TruncatedListError> {
         if self.data.len() <= DisplayListItem::max_size() {
-            return (None, None);
+            return Ok((None, None));
         }
 
         let mut item = item;
@@ -110,112 +463,260 @@ impl<'a> DisplayListIter<'a> {
         if let DisplayListItem::List = item {
             let mut skip = 0usize;
             self.data = peek_from_slice(self.data, &mut skip);
+            if skip > self.data.len() {
+                return Err(TruncatedListError);
+            }
             let (skip, rest) = self.data.split_at(skip);
             self.data = rest;
-            return (Some(item), Some(skip));
+            return Ok((Some(item), Some(DisplayListIter::new(skip))));
         }
 
-        (Some(item), None)
+        Ok((Some(item), None))
     }
 }
 
 struct Backend {
     receiver: Receiver<Message>,
     result_sender: Sender<()>,
+    /// One status channel per producer; backpressure transitions are
+    /// broadcast to all of them.
+    status_senders: Vec<Sender<PayloadStatus>>,
 }
 
 impl Backend {
-    fn new(receiver: Receiver<Message>, result_sender: Sender<()>) -> Self {
+    fn new(
+        receiver: Receiver<Message>,
+        result_sender: Sender<()>,
+        status_senders: Vec<Sender<PayloadStatus>>,
+    ) -> Self {
         Backend {
             receiver,
             result_sender,
+            status_senders,
+        }
+    }
+
+    fn broadcast_status(&self, status: PayloadStatus) {
+        for status_sender in &self.status_senders {
+            // A producer may already have finished and dropped its receiver;
+            // that's not a reason to stop serving the others.
+            let _ = status_sender.send(status);
         }
     }
 
     fn run(&self) {
+        let mut queue = SendQueue::new();
+        let mut reassembler = ChunkReassembler::new();
+        let mut reorder = ReorderBuffer::new();
+        let mut pending_bytes = 0usize;
+        let mut producers_paused = false;
         loop {
-            match self.receiver.recv().expect("Could not receive Message") {
-                Message::SetDisplayList(dl) => {
-                    let iter = dl.iter();
-                    self.process(iter);
-                    self.result_sender.send(()).expect("Could not send result");
+            // Block until at least one message is available, then drain
+            // whatever else has already arrived before picking the next
+            // message to process, so priority has something to sort.
+            match self.receiver.recv() {
+                Ok(message) => {
+                    if let Message::DisplayListChunk { ref frame, .. } = message {
+                        pending_bytes += frame.len();
+                    }
+                    queue.push(message);
+                }
+                Err(_) => break,
+            }
+            while let Ok(message) = self.receiver.try_recv() {
+                if let Message::DisplayListChunk { ref frame, .. } = message {
+                    pending_bytes += frame.len();
                 }
-                Message::Close => break,
-            };
+                queue.push(message);
+            }
+
+            if !producers_paused && pending_bytes > MAX_BUFFER_SIZE {
+                producers_paused = true;
+                self.broadcast_status(PayloadStatus::Pause);
+            }
+
+            // `Close` is the highest priority so a pending shutdown isn't
+            // starved behind a backlog of display lists, but that also means
+            // it can be popped before chunks queued alongside it in the same
+            // batch. Don't honor it until the rest of this batch has been
+            // processed, or those chunks would be silently dropped.
+            let mut close_requested = false;
+            while let Some(message) = queue.pop() {
+                match message {
+                    Message::DisplayListChunk { seq, frame, .. } => {
+                        // Mirror the increment above exactly (whole frame,
+                        // header included), so a long-running producer can't
+                        // leak bytes that never drain back out.
+                        pending_bytes = pending_bytes.saturating_sub(frame.len());
+                        if let Some(payload) = reassembler.push_frame(seq, &frame) {
+                            reorder.insert(seq, payload);
+                        }
+                        while let Some(payload) = reorder.pop_ready() {
+                            let iter = DisplayListIter::new(&payload);
+                            if let Err(err) = self.process(iter) {
+                                eprintln!("Dropping malformed display list: {}", err);
+                            }
+                            self.result_sender.send(()).expect("Could not send result");
+                        }
+                    }
+                    Message::Close => close_requested = true,
+                }
+            }
+
+            if close_requested {
+                self.broadcast_status(PayloadStatus::Dropped);
+                return;
+            }
+
+            if producers_paused && pending_bytes <= LOW_WATERMARK {
+                producers_paused = false;
+                self.broadcast_status(PayloadStatus::Read);
+            }
         }
     }
 
-    fn process(&self, iter: DisplayListIter) {
+    /// Walks `iter` to arbitrary depth: a `List` whose items are themselves
+    /// `List`s is decoded by recursing into each nested scope, honoring that
+    /// scope's own length header and red zone terminator.
+    fn process(&self, iter: DisplayListIter) -> Result<(), TruncatedListError> {
         let mut iter = iter;
         loop {
-            match iter.next_payload_as_item(DisplayListItem::None) {
-                (Some(item), skip) => {
+            match iter.next_payload_as_item(DisplayListItem::None)? {
+                (Some(item), Some(sub_iter)) => {
+                    println!("Get DisplayItem in backend thread: {:?}", item);
+                    self.process(sub_iter.skip_len_header())?;
+                }
+                (Some(item), None) => {
                     println!("Get DisplayItem in backend thread: {:?}", item);
-
-                    let mut data = match skip {
-                        Some(v) => v,
-                        None => continue,
-                    };
-
-                    let mut item = DisplayListItem::None;
-
-                    // Get array size from `data`
-                    let mut size = 0usize;
-                    if !data.is_empty() {
-                        data = peek_from_slice(data, &mut size);
-                    }
-
-                    loop {
-                        if size == 0 {
-                            break;
-                        }
-                        size -= 1;
-
-                        data = peek_from_slice(data, &mut item);
-                        println!("Get DisplayItem::List in backend thread: {:?}", item);
-                    }
                 }
-                _ => break,
-            };
+                (None, _) => break,
+            }
         }
+        Ok(())
     }
 }
 
+/// Number of concurrent producer threads feeding the backend pool.
+const PRODUCER_COUNT: u64 = 3;
+
+/// Number of backend worker threads display lists are partitioned across.
+const PARTITION_COUNT: usize = 2;
+
+fn build_display_list(seq: u64, foreground_priority: RequestPriority) -> PartitionedDisplayList {
+    let mut display_list = PartitionedDisplayList::new(PARTITION_COUNT, seq as usize);
+    display_list.push_item(
+        &DisplayListItem::Rect(RectItem {
+            min: (350. * seq as f32, 100.),
+            max: (500. * seq as f32, 500.),
+        }),
+        Some(foreground_priority),
+    );
+    display_list.push_item(
+        &DisplayListItem::Rect(RectItem {
+            min: (500., 500.),
+            max: (1000., 1000.),
+        }),
+        None,
+    );
+
+    display_list.push_list(
+        [DisplayListItem::ListItem, DisplayListItem::ListItem],
+        Some(PRIO_BACKGROUND),
+    );
+
+    display_list.end();
+    display_list
+}
+
 fn main() {
-    let (sender, receiver) = unbounded();
     let (result_sender, result_receiver) = unbounded();
 
-    let backend_thread_name = "backend".to_owned();
-    thread::Builder::new()
-        .name(backend_thread_name)
-        .spawn(move || {
-            let b = Backend::new(receiver, result_sender);
-            b.run();
-        })
-        .expect("Backend thread could not spawn");
+    // One channel pair per backend worker...
+    let mut senders = Vec::with_capacity(PARTITION_COUNT);
+    let mut receivers = Vec::with_capacity(PARTITION_COUNT);
+    for _ in 0..PARTITION_COUNT {
+        let (sender, receiver) = unbounded();
+        senders.push(sender);
+        receivers.push(receiver);
+    }
 
-    let mut display_list = DisplayList::new();
-    display_list.push_item(&DisplayListItem::Rect(RectItem {
-        min: (100., 100.),
-        max: (500., 500.),
-    }));
-    display_list.push_item(&DisplayListItem::Rect(RectItem {
-        min: (500., 500.),
-        max: (1000., 1000.),
-    }));
+    // ...and, per worker, one flow-control channel per producer feeding it,
+    // so every producer receives each pause and resume the worker broadcasts.
+    let mut status_senders: Vec<Vec<Sender<PayloadStatus>>> =
+        (0..PARTITION_COUNT).map(|_| Vec::new()).collect();
+    let mut status_receivers: Vec<Vec<Receiver<PayloadStatus>>> =
+        (0..PRODUCER_COUNT).map(|_| Vec::new()).collect();
+    for partition_status_senders in status_senders.iter_mut() {
+        for producer_status_receivers in status_receivers.iter_mut() {
+            let (status_sender, status_receiver) = unbounded();
+            partition_status_senders.push(status_sender);
+            producer_status_receivers.push(status_receiver);
+        }
+    }
 
-    display_list.push_list([DisplayListItem::ListItem, DisplayListItem::ListItem]);
+    let backend_workers: Vec<_> = receivers
+        .into_iter()
+        .zip(status_senders)
+        .enumerate()
+        .map(|(partition, (receiver, partition_status_senders))| {
+            let result_sender = result_sender.clone();
+            thread::Builder::new()
+                .name(format!("backend-{}", partition))
+                .spawn(move || {
+                    let b = Backend::new(receiver, result_sender, partition_status_senders);
+                    b.run();
+                })
+                .expect("Backend thread could not spawn")
+        })
+        .collect();
+
+    // Every producer shares one `seq` counter, so each worker can reassemble
+    // the lists it receives in the order they were created rather than the
+    // order their chunks happen to arrive in.
+    let next_seq = Arc::new(AtomicU64::new(0));
+    let producers: Vec<_> = status_receivers
+        .into_iter()
+        .enumerate()
+        .map(|(i, producer_status_receivers)| {
+            let mut partition_senders: Vec<FlowControlledSender> = senders
+                .iter()
+                .cloned()
+                .zip(producer_status_receivers)
+                .map(|(sender, status_receiver)| FlowControlledSender::new(sender, status_receiver))
+                .collect();
+            let next_seq = Arc::clone(&next_seq);
+            // Producer 0 ships a foreground list that should preempt the rest;
+            // the others ship background lists, so the queue actually has to
+            // choose between priorities instead of every list landing at the
+            // same one.
+            let foreground_priority = if i == 0 { PRIO_HIGH } else { PRIO_BACKGROUND };
+            thread::Builder::new()
+                .name(format!("producer-{}", i))
+                .spawn(move || {
+                    let seq = next_seq.fetch_add(1, Ordering::SeqCst);
+                    let display_list = build_display_list(seq, foreground_priority);
+                    display_list.send_chunked(seq, &mut partition_senders);
+                    println!("Send display_list seq={}", seq);
+                })
+                .expect("Producer thread could not spawn")
+        })
+        .collect();
 
-    display_list.end();
+    for producer in producers {
+        producer.join().expect("Producer thread panicked");
+    }
 
-    sender
-        .send(Message::SetDisplayList(display_list))
-        .expect("Could not send display_list");
-    println!("Send display_list");
+    for _ in 0..(PRODUCER_COUNT as usize * PARTITION_COUNT) {
+        result_receiver.recv().expect("Could not receive result");
+    }
 
-    result_receiver.recv().expect("Could not receive result");
+    for sender in &senders {
+        sender
+            .send(Message::Close)
+            .expect("Could not send close message");
+    }
 
-    sender
-        .send(Message::Close)
-        .expect("Could not send close message");
+    for worker in backend_workers {
+        worker.join().expect("Backend thread panicked");
+    }
 }